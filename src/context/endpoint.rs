@@ -0,0 +1,61 @@
+//! The Endpoint Context.
+
+use super::{ro_field, rw_field, Dwords};
+use bit_field::BitField;
+
+/// The Endpoint Context: the state of a single endpoint (type, max packet size, its Transfer
+/// Ring, ...).
+///
+/// A [`super::DeviceContext`] (and the device-context portion of an [`super::InputContext`])
+/// holds one of these per Endpoint Context Index, in addition to the [`super::SlotContext`].
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct EndpointContext([u32; 8]);
+impl Dwords for EndpointContext {
+    fn dword(&self, i: usize) -> u32 {
+        self.0[i]
+    }
+
+    fn set_dword(&mut self, i: usize, value: u32) {
+        self.0[i] = value;
+    }
+}
+impl EndpointContext {
+    ro_field!(0, 0..=2, endpoint_state, "Endpoint State", u8);
+    rw_field!(0, 8..=9, mult, set_mult, "Mult", u8);
+    rw_field!(0, 10..=14, max_primary_streams, set_max_primary_streams, "Max Primary Streams", u8);
+    rw_field!(0, 16..=23, interval, set_interval, "Interval", u8);
+    rw_field!(1, 1..=2, error_count, set_error_count, "Error Count", u8);
+    rw_field!(1, 3..=5, endpoint_type, set_endpoint_type, "Endpoint Type", u8);
+    rw_field!(1, 8..=15, max_burst_size, set_max_burst_size, "Max Burst Size", u8);
+    rw_field!(1, 16..=31, max_packet_size, set_max_packet_size, "Max Packet Size", u16);
+    rw_field!(4, 0..=15, average_trb_length, set_average_trb_length, "Average TRB Length", u16);
+    rw_field!(4, 16..=31, max_esit_payload_low, set_max_esit_payload_low, "Max Endpoint Service Time Interval Payload Low", u16);
+
+    /// Returns the value of the TR Dequeue Pointer field.
+    #[must_use]
+    pub fn tr_dequeue_pointer(self) -> u64 {
+        (u64::from(self.dword(3)) << 32) | u64::from(self.dword(2) & !0b1111)
+    }
+
+    /// Returns the value of the Dequeue Cycle State bit.
+    #[must_use]
+    pub fn dequeue_cycle_state(self) -> bool {
+        self.dword(2).get_bit(0)
+    }
+
+    /// Sets the value of the TR Dequeue Pointer field and the Dequeue Cycle State bit.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `pointer` is not 16-byte aligned.
+    pub fn set_tr_dequeue_pointer(&mut self, pointer: u64, dequeue_cycle_state: bool) {
+        assert!(
+            pointer.trailing_zeros() >= 4,
+            "The TR Dequeue Pointer must be 16-byte aligned."
+        );
+
+        self.set_dword(2, (pointer as u32 & !0b1111) | u32::from(dequeue_cycle_state));
+        self.set_dword(3, (pointer >> 32) as u32);
+    }
+}
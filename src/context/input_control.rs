@@ -0,0 +1,65 @@
+//! The Input Control Context.
+
+use super::{rw_field, Dwords};
+use bit_field::BitField;
+
+/// The Input Control Context: the first context of an [`super::InputContext`], selecting which
+/// of the other contexts a Configure Endpoint or Evaluate Context command should evaluate (Add
+/// Context flags) or disable (Drop Context flags).
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct InputControlContext([u32; 8]);
+impl Dwords for InputControlContext {
+    fn dword(&self, i: usize) -> u32 {
+        self.0[i]
+    }
+
+    fn set_dword(&mut self, i: usize, value: u32) {
+        self.0[i] = value;
+    }
+}
+impl InputControlContext {
+    /// Returns the value of the Drop Context flag for the given Endpoint Context Index (2..=31;
+    /// D0 and D1 are reserved).
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `dci` is not in the range `2..=31`.
+    #[must_use]
+    pub fn drop_context_flag(self, dci: u8) -> bool {
+        assert!((2..=31).contains(&dci), "dci must be in the range 2..=31");
+
+        self.dword(0).get_bit(dci.into())
+    }
+
+    /// Sets the value of the Drop Context flag for the given Endpoint Context Index.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `dci` is not in the range `2..=31`.
+    pub fn set_drop_context_flag(&mut self, dci: u8, drop: bool) {
+        assert!((2..=31).contains(&dci), "dci must be in the range 2..=31");
+
+        let mut d0 = self.dword(0);
+        d0.set_bit(dci.into(), drop);
+        self.set_dword(0, d0);
+    }
+
+    /// Returns the value of the Add Context flag for the given Context Index (0 is the Slot
+    /// Context, 1..=31 are Endpoint Context Indices).
+    #[must_use]
+    pub fn add_context_flag(self, context_index: u8) -> bool {
+        self.dword(1).get_bit(context_index.into())
+    }
+
+    /// Sets the value of the Add Context flag for the given Context Index.
+    pub fn set_add_context_flag(&mut self, context_index: u8, add: bool) {
+        let mut d1 = self.dword(1);
+        d1.set_bit(context_index.into(), add);
+        self.set_dword(1, d1);
+    }
+
+    rw_field!(7, 0..=7, configuration_value, set_configuration_value, "Configuration Value", u8);
+    rw_field!(7, 8..=15, interface_number, set_interface_number, "Interface Number", u8);
+    rw_field!(7, 16..=23, alternate_setting, set_alternate_setting, "Alternate Setting", u8);
+}
@@ -0,0 +1,337 @@
+//! The Device Context, the Input Context, and the Device Context Base Address Array.
+//!
+//! These are the data structures a driver fills in to address a USB device and configure its
+//! endpoints: a [`DeviceContext`] describes what the controller currently knows about a slot, and
+//! an [`InputContext`] is what a driver builds and hands to an Address Device or Configure
+//! Endpoint command to change that state.
+//!
+//! Both are arrays of fixed-size contexts, but the size of each entry depends on the HCCPARAMS1
+//! Context Size (CSZ) bit: 32 bytes if it is 0, or 64 bytes (with the trailing 32 bytes reserved)
+//! if it is 1. [`ContextSize`] carries that choice at runtime, since it can only be read from the
+//! hardware, not known at compile time.
+
+mod endpoint;
+mod input_control;
+mod slot;
+
+pub use endpoint::EndpointContext;
+pub use input_control::InputControlContext;
+pub use slot::SlotContext;
+
+use bit_field::BitField;
+use core::convert::TryInto;
+
+/// A type whose storage is a fixed number of consecutive `u32` dwords, addressable by index.
+///
+/// This lets the `rw_field!`/`ro_field!`/`rw_bit!`/`ro_bit!` macros below target a specific dword
+/// of a context, the same way [`crate::ring::trb`]'s macros of the same name target a dword of a
+/// TRB.
+pub(crate) trait Dwords {
+    /// Returns the value of the `i`-th dword.
+    fn dword(&self, i: usize) -> u32;
+    /// Sets the value of the `i`-th dword.
+    fn set_dword(&mut self, i: usize, value: u32);
+}
+
+/// Implements the getter/setter pair for a read-write field inside one dword of a [`Dwords`]
+/// type.
+macro_rules! rw_field {
+    ($dword:expr, $range:expr, $getter:ident, $setter:ident, $name:literal, $ty:ty) => {
+        #[doc = concat!("Returns the value of the ", $name, " field.")]
+        #[must_use]
+        pub fn $getter(self) -> $ty {
+            self.dword($dword).get_bits($range).try_into().unwrap()
+        }
+
+        #[doc = concat!("Sets the value of the ", $name, " field.")]
+        pub fn $setter(&mut self, value: $ty) {
+            let mut d = self.dword($dword);
+            d.set_bits($range, value.into());
+            self.set_dword($dword, d);
+        }
+    };
+}
+pub(crate) use rw_field;
+
+/// Implements the getter for a read-only field inside one dword of a [`Dwords`] type.
+macro_rules! ro_field {
+    ($dword:expr, $range:expr, $getter:ident, $name:literal, $ty:ty) => {
+        #[doc = concat!("Returns the value of the ", $name, " field.")]
+        #[must_use]
+        pub fn $getter(self) -> $ty {
+            self.dword($dword).get_bits($range).try_into().unwrap()
+        }
+    };
+}
+pub(crate) use ro_field;
+
+/// Implements the getter/setter pair for a read-write bit inside one dword of a [`Dwords`] type.
+macro_rules! rw_bit {
+    ($dword:expr, $bit:expr, $getter:ident, $setter:ident, $name:literal) => {
+        #[doc = concat!("Returns the value of the ", $name, " bit.")]
+        #[must_use]
+        pub fn $getter(self) -> bool {
+            self.dword($dword).get_bit($bit)
+        }
+
+        #[doc = concat!("Sets the value of the ", $name, " bit.")]
+        pub fn $setter(&mut self, value: bool) {
+            let mut d = self.dword($dword);
+            d.set_bit($bit, value);
+            self.set_dword($dword, d);
+        }
+    };
+}
+pub(crate) use rw_bit;
+
+/// Implements the getter for a read-only bit inside one dword of a [`Dwords`] type.
+macro_rules! ro_bit {
+    ($dword:expr, $bit:expr, $getter:ident, $name:literal) => {
+        #[doc = concat!("Returns the value of the ", $name, " bit.")]
+        #[must_use]
+        pub fn $getter(self) -> bool {
+            self.dword($dword).get_bit($bit)
+        }
+    };
+}
+pub(crate) use ro_bit;
+
+/// The largest valid Endpoint Context Index (`EP Context Index`); device context index 0 is the
+/// Slot Context, and indices 1..=31 are Endpoint Contexts.
+const MAX_CONTEXT_INDEX: u8 = 31;
+
+/// Whether the HCCPARAMS1 Context Size (CSZ) bit selects 32-byte or 64-byte contexts.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ContextSize {
+    /// CSZ = 0: each context is 32 bytes.
+    Csz32,
+    /// CSZ = 1: each context is 64 bytes; the trailing 32 bytes of each are reserved.
+    Csz64,
+}
+impl ContextSize {
+    /// Returns the byte stride between two consecutive contexts.
+    #[must_use]
+    pub fn stride(self) -> usize {
+        match self {
+            ContextSize::Csz32 => 32,
+            ContextSize::Csz64 => 64,
+        }
+    }
+}
+
+/// A runtime-selectable-layout array of contexts: a [`SlotContext`] at index 0, followed by up
+/// to 31 [`EndpointContext`]s.
+///
+/// This is the shape shared by a [`DeviceContext`] and the device-context portion of an
+/// [`InputContext`].
+#[derive(Debug)]
+pub struct ContextArray<'a> {
+    raw: &'a mut [u8],
+    context_size: ContextSize,
+}
+impl<'a> ContextArray<'a> {
+    /// Wraps `raw`, a buffer of 32 contexts (a Slot Context and 31 Endpoint Contexts) laid out
+    /// with `context_size`.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `raw` is too small to hold 32 contexts of `context_size`.
+    #[must_use]
+    pub fn new(raw: &'a mut [u8], context_size: ContextSize) -> Self {
+        assert!(
+            raw.len() >= context_size.stride() * (usize::from(MAX_CONTEXT_INDEX) + 1),
+            "The buffer is too small to hold a full Slot Context plus 31 Endpoint Contexts."
+        );
+
+        Self { raw, context_size }
+    }
+
+    fn offset(&self, context_index: u8) -> usize {
+        usize::from(context_index) * self.context_size.stride()
+    }
+
+    /// Returns a reference to the Slot Context.
+    #[must_use]
+    pub fn slot(&self) -> &SlotContext {
+        unsafe { &*(self.raw.as_ptr().cast()) }
+    }
+
+    /// Returns a mutable reference to the Slot Context.
+    pub fn slot_mut(&mut self) -> &mut SlotContext {
+        unsafe { &mut *(self.raw.as_mut_ptr().cast()) }
+    }
+
+    /// Returns a reference to the Endpoint Context at the given Endpoint Context Index
+    /// (`1..=31`).
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `dci` is not in the range `1..=31`.
+    #[must_use]
+    pub fn endpoint(&self, dci: u8) -> &EndpointContext {
+        assert!(
+            (1..=MAX_CONTEXT_INDEX).contains(&dci),
+            "dci must be in the range 1..=31"
+        );
+
+        unsafe { &*(self.raw[self.offset(dci)..].as_ptr().cast()) }
+    }
+
+    /// Returns a mutable reference to the Endpoint Context at the given Endpoint Context Index
+    /// (`1..=31`).
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `dci` is not in the range `1..=31`.
+    pub fn endpoint_mut(&mut self, dci: u8) -> &mut EndpointContext {
+        assert!(
+            (1..=MAX_CONTEXT_INDEX).contains(&dci),
+            "dci must be in the range 1..=31"
+        );
+
+        let offset = self.offset(dci);
+        unsafe { &mut *(self.raw[offset..].as_mut_ptr().cast()) }
+    }
+}
+
+/// What the controller currently knows about a device slot: its [`SlotContext`] and the
+/// [`EndpointContext`] of each of its endpoints.
+#[derive(Debug)]
+pub struct DeviceContext<'a>(ContextArray<'a>);
+impl<'a> DeviceContext<'a> {
+    /// Wraps `raw`, a buffer already sized for the given `context_size`. See
+    /// [`ContextArray::new`] for the layout and size requirements.
+    #[must_use]
+    pub fn new(raw: &'a mut [u8], context_size: ContextSize) -> Self {
+        Self(ContextArray::new(raw, context_size))
+    }
+
+    /// Returns a reference to the Slot Context.
+    #[must_use]
+    pub fn slot(&self) -> &SlotContext {
+        self.0.slot()
+    }
+
+    /// Returns a mutable reference to the Slot Context.
+    pub fn slot_mut(&mut self) -> &mut SlotContext {
+        self.0.slot_mut()
+    }
+
+    /// Returns a reference to the Endpoint Context at the given Endpoint Context Index.
+    #[must_use]
+    pub fn endpoint(&self, dci: u8) -> &EndpointContext {
+        self.0.endpoint(dci)
+    }
+
+    /// Returns a mutable reference to the Endpoint Context at the given Endpoint Context Index.
+    pub fn endpoint_mut(&mut self, dci: u8) -> &mut EndpointContext {
+        self.0.endpoint_mut(dci)
+    }
+}
+
+/// What a driver builds to change the state of a slot: an [`InputControlContext`] selecting which
+/// of the following contexts to evaluate, followed by the same Slot/Endpoint Context layout as a
+/// [`DeviceContext`].
+#[derive(Debug)]
+pub struct InputContext<'a> {
+    control: &'a mut [u8],
+    device: ContextArray<'a>,
+}
+impl<'a> InputContext<'a> {
+    /// Wraps `control` (one context's worth of storage, holding the Input Control Context) and
+    /// `device` (32 contexts' worth of storage, holding the Slot and Endpoint Contexts), both
+    /// laid out with `context_size`.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `control` is smaller than one context, or if `device` is too small
+    /// to hold 32 contexts. See [`ContextArray::new`].
+    #[must_use]
+    pub fn new(control: &'a mut [u8], device: &'a mut [u8], context_size: ContextSize) -> Self {
+        assert!(
+            control.len() >= context_size.stride(),
+            "The Input Control Context buffer is too small."
+        );
+
+        Self {
+            control,
+            device: ContextArray::new(device, context_size),
+        }
+    }
+
+    /// Returns a reference to the Input Control Context.
+    #[must_use]
+    pub fn control(&self) -> &InputControlContext {
+        unsafe { &*(self.control.as_ptr().cast()) }
+    }
+
+    /// Returns a mutable reference to the Input Control Context.
+    pub fn control_mut(&mut self) -> &mut InputControlContext {
+        unsafe { &mut *(self.control.as_mut_ptr().cast()) }
+    }
+
+    /// Returns a reference to the Slot Context.
+    #[must_use]
+    pub fn slot(&self) -> &SlotContext {
+        self.device.slot()
+    }
+
+    /// Returns a mutable reference to the Slot Context.
+    pub fn slot_mut(&mut self) -> &mut SlotContext {
+        self.device.slot_mut()
+    }
+
+    /// Returns a reference to the Endpoint Context at the given Endpoint Context Index.
+    #[must_use]
+    pub fn endpoint(&self, dci: u8) -> &EndpointContext {
+        self.device.endpoint(dci)
+    }
+
+    /// Returns a mutable reference to the Endpoint Context at the given Endpoint Context Index.
+    pub fn endpoint_mut(&mut self, dci: u8) -> &mut EndpointContext {
+        self.device.endpoint_mut(dci)
+    }
+}
+
+/// The Device Context Base Address Array: indexed by Slot ID, it points the controller at each
+/// slot's [`DeviceContext`] (index 0 instead holds the Scratchpad Buffer Array pointer, if any).
+#[derive(Debug)]
+pub struct DeviceContextBaseAddressArray<'a>(&'a mut [u64]);
+impl<'a> DeviceContextBaseAddressArray<'a> {
+    /// Wraps `entries`, one `u64` per Slot ID (plus index 0 for the Scratchpad Buffer Array).
+    #[must_use]
+    pub fn new(entries: &'a mut [u64]) -> Self {
+        Self(entries)
+    }
+
+    /// Points Slot ID `slot_id` at the Device Context located at `device_context_physical_base`.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `device_context_physical_base` is not 64-byte aligned.
+    pub fn set(&mut self, slot_id: u8, device_context_physical_base: u64) {
+        assert!(
+            device_context_physical_base.trailing_zeros() >= 6,
+            "A Device Context must be 64-byte aligned."
+        );
+
+        self.0[usize::from(slot_id)] = device_context_physical_base;
+    }
+
+    /// Returns the physical address to program into the Device Context Base Address Array
+    /// Pointer Register (DCBAAP), given `array_physical_base`, the physical address of the
+    /// array's backing memory.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `array_physical_base` is not 64-byte aligned.
+    #[must_use]
+    pub fn dcbaap(&self, array_physical_base: u64) -> u64 {
+        assert!(
+            array_physical_base.trailing_zeros() >= 6,
+            "The Device Context Base Address Array must be 64-byte aligned."
+        );
+
+        array_physical_base
+    }
+}
@@ -0,0 +1,35 @@
+//! The Slot Context.
+
+use super::{ro_field, rw_bit, rw_field, Dwords};
+
+/// The Slot Context: the device-wide state of a slot (Route String, Speed, ...).
+///
+/// This is always the first context inside a [`super::DeviceContext`] (and, inside a
+/// [`super::InputContext`], the first context after the Input Control Context).
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct SlotContext([u32; 8]);
+impl Dwords for SlotContext {
+    fn dword(&self, i: usize) -> u32 {
+        self.0[i]
+    }
+
+    fn set_dword(&mut self, i: usize, value: u32) {
+        self.0[i] = value;
+    }
+}
+impl SlotContext {
+    rw_field!(0, 0..=19, route_string, set_route_string, "Route String", u32);
+    rw_field!(0, 20..=23, speed, set_speed, "Speed", u8);
+    rw_field!(0, 27..=31, context_entries, set_context_entries, "Context Entries", u8);
+    rw_field!(1, 0..=15, max_exit_latency, set_max_exit_latency, "Max Exit Latency", u16);
+    rw_field!(1, 16..=23, root_hub_port_number, set_root_hub_port_number, "Root Hub Port Number", u8);
+    rw_field!(1, 24..=31, number_of_ports, set_number_of_ports, "Number of Ports", u8);
+    rw_field!(2, 0..=7, parent_hub_slot_id, set_parent_hub_slot_id, "Parent Hub Slot ID", u8);
+    rw_field!(2, 8..=15, parent_port_number, set_parent_port_number, "Parent Port Number", u8);
+    rw_field!(2, 22..=31, interrupter_target, set_interrupter_target, "Interrupter Target", u16);
+    ro_field!(3, 0..=7, usb_device_address, "USB Device Address", u8);
+    ro_field!(3, 27..=31, slot_state, "Slot State", u8);
+    rw_bit!(0, 25, multi_tt, set_multi_tt, "Multi-TT (MTT)");
+    rw_bit!(0, 26, hub, set_hub, "Hub");
+}
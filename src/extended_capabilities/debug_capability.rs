@@ -0,0 +1,299 @@
+//! A high-level driver for the Debug Capability (DbC), built on top of [`super::debug::Debug`].
+//!
+//! [`super::debug::Debug`] only wires up the raw DbC registers; this module owns the DbC Context,
+//! the two DbC transfer rings (OUT and IN), and the DbC event ring, and drives the bring-up
+//! sequence described in the xHCI specification (and implemented by Linux's `xhci-dbgcap.c`).
+
+use super::debug::Debug;
+use crate::context::EndpointContext as DeviceEndpointContext;
+use crate::ring::erst::advance_dequeue;
+use crate::ring::erst::EventRingSegmentTable;
+use crate::ring::Ring;
+use crate::ring::Trb;
+use accessor::Mapper;
+use bit_field::BitField;
+use core::convert::TryInto;
+
+/// The DbC Context: an Info Context followed by the OUT and IN Endpoint Contexts.
+///
+/// Each of the three sub-contexts is 64 bytes, placing the Info Context at offset 0x00, the OUT
+/// Endpoint Context at 0x40, and the IN Endpoint Context at 0x80, per the xHCI specification.
+/// This mirrors `struct xdbc_context` in Linux's `xhci-dbgcap.h`. The whole structure is what
+/// `dccp` must point at.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct DbcContext {
+    /// The Info Context.
+    pub info: InfoContext,
+    /// The OUT Endpoint Context (the endpoint the host writes to).
+    pub out_endpoint: EndpointContext,
+    /// The IN Endpoint Context (the endpoint the host reads from).
+    pub in_endpoint: EndpointContext,
+}
+
+/// The Info Context: the string descriptors a DbC-aware host shows the user, and their lengths.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct InfoContext {
+    string0_descriptor_address: u64,
+    manufacturer_string_descriptor_address: u64,
+    product_string_descriptor_address: u64,
+    serial_number_string_descriptor_address: u64,
+    lengths: u32,
+    _reserved: [u32; 7],
+}
+impl InfoContext {
+    /// Sets the address and length of the String0 (language ID) descriptor.
+    pub fn set_string0(&mut self, address: u64, length: u8) -> &mut Self {
+        self.string0_descriptor_address = address;
+        self.lengths.set_bits(0..=7, length.into());
+        self
+    }
+
+    /// Sets the address and length of the Manufacturer string descriptor.
+    pub fn set_manufacturer_string(&mut self, address: u64, length: u8) -> &mut Self {
+        self.manufacturer_string_descriptor_address = address;
+        self.lengths.set_bits(8..=15, length.into());
+        self
+    }
+
+    /// Sets the address and length of the Product string descriptor.
+    pub fn set_product_string(&mut self, address: u64, length: u8) -> &mut Self {
+        self.product_string_descriptor_address = address;
+        self.lengths.set_bits(16..=23, length.into());
+        self
+    }
+
+    /// Sets the address and length of the Serial Number string descriptor.
+    pub fn set_serial_number_string(&mut self, address: u64, length: u8) -> &mut Self {
+        self.serial_number_string_descriptor_address = address;
+        self.lengths.set_bits(24..=31, length.into());
+        self
+    }
+}
+
+/// An Endpoint Context, as it appears inside a [`DbcContext`].
+///
+/// This is a [`crate::context::EndpointContext`] (32 bytes) padded out to the 64 bytes a DbC
+/// sub-context requires; the trailing 32 bytes are reserved, matching
+/// `xdbc_ep_context.reserved[11]` once the 3 reserved dwords already inside
+/// [`crate::context::EndpointContext`] are counted.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct EndpointContext {
+    inner: DeviceEndpointContext,
+    _reserved: [u32; 8],
+}
+impl core::ops::Deref for EndpointContext {
+    type Target = DeviceEndpointContext;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+impl core::ops::DerefMut for EndpointContext {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+/// The physical address and length of a single USB string descriptor.
+#[derive(Copy, Clone, Debug)]
+pub struct StringDescriptor {
+    /// The physical address of the string descriptor.
+    pub address: u64,
+    /// The length of the string descriptor, in bytes.
+    pub length: u8,
+}
+
+/// The string descriptor table a DbC presents to the host that enumerates it: the String0
+/// (language ID), Manufacturer, Product, and Serial Number descriptors.
+#[derive(Copy, Clone, Debug)]
+pub struct StringDescriptors {
+    /// The String0 (language ID) descriptor.
+    pub string0: StringDescriptor,
+    /// The Manufacturer string descriptor.
+    pub manufacturer: StringDescriptor,
+    /// The Product string descriptor.
+    pub product: StringDescriptor,
+    /// The Serial Number string descriptor.
+    pub serial_number: StringDescriptor,
+}
+
+/// The identifying information a DbC presents to the host that enumerates it.
+#[derive(Copy, Clone, Debug)]
+pub struct DeviceIdentity {
+    /// The USB Vendor ID.
+    pub vendor_id: u16,
+    /// The USB Product ID.
+    pub product_id: u16,
+    /// The device revision (BCD).
+    pub device_revision: u16,
+    /// The DbC protocol (0 for the vendor-defined protocol, 1 for the GNU Remote Debug Protocol).
+    pub protocol: u8,
+}
+
+/// The backing memory a [`DebugCapability`] needs, and where it sits in physical memory.
+///
+/// The caller is responsible for allocating this (DMA-capable) memory through the [`Mapper`] and
+/// for keeping it alive for as long as the [`DebugCapability`] is in use.
+pub struct DbcMemory<'a> {
+    /// The DbC Context and its physical address.
+    pub context: (&'a mut DbcContext, u64),
+    /// The Event Ring Segment Table (a single segment) and its physical address.
+    pub erst: (&'a mut [crate::ring::erst::EventRingSegmentTableEntry], u64),
+    /// The event ring's TRB buffer and its physical address.
+    pub event_ring: (&'a mut [Trb], u64),
+    /// The OUT transfer ring's TRB buffer and its physical address.
+    pub out_ring: (&'a mut [Trb], u64),
+    /// The IN transfer ring's TRB buffer and its physical address.
+    pub in_ring: (&'a mut [Trb], u64),
+}
+
+/// A driver for the Debug Capability: owns the DbC Context, its two transfer rings, and its
+/// event ring, and drives the bring-up sequence of the xHCI specification.
+pub struct DebugCapability<'a, M>
+where
+    M: Mapper + Clone,
+{
+    debug: Debug<M>,
+    out_ring: Ring<'a>,
+    in_ring: Ring<'a>,
+    event_ring_trbs: &'a [Trb],
+    event_ring_physical_base: u64,
+    event_ring_segment_index: usize,
+    event_ring_trb_index: usize,
+    event_ring_cycle_state: bool,
+}
+impl<'a, M> DebugCapability<'a, M>
+where
+    M: Mapper + Clone,
+{
+    /// Brings up the Debug Capability: builds the DbC Context (including its string descriptor
+    /// table) and Event Ring Segment Table, sets up the two transfer rings, programs every DbC
+    /// register, then enables the capability and polls until the host has connected and
+    /// `dbc_run` is set.
+    ///
+    /// This is a blocking, best-effort bring-up; see the individual `dccp`/`dcctrl` register
+    /// accessors on [`Debug`] for a lower-level, resumable alternative.
+    pub fn init(
+        mut debug: Debug<M>,
+        memory: DbcMemory<'a>,
+        identity: DeviceIdentity,
+        strings: StringDescriptors,
+    ) -> Self {
+        let DbcMemory {
+            context: (context, context_address),
+            erst: (erst_entries, erst_address),
+            event_ring: (event_ring_trbs, event_ring_address),
+            out_ring: (out_ring_trbs, out_ring_address),
+            in_ring: (in_ring_trbs, in_ring_address),
+        } = memory;
+
+        context
+            .info
+            .set_string0(strings.string0.address, strings.string0.length)
+            .set_manufacturer_string(strings.manufacturer.address, strings.manufacturer.length)
+            .set_product_string(strings.product.address, strings.product.length)
+            .set_serial_number_string(strings.serial_number.address, strings.serial_number.length);
+
+        context.out_endpoint.set_endpoint_type(2);
+        context.out_endpoint.set_max_packet_size(1024);
+        context.out_endpoint.set_max_burst_size(0);
+        context.out_endpoint.set_average_trb_length(1024);
+        context
+            .out_endpoint
+            .set_tr_dequeue_pointer(out_ring_address, true);
+
+        context.in_endpoint.set_endpoint_type(6);
+        context.in_endpoint.set_max_packet_size(1024);
+        context.in_endpoint.set_max_burst_size(0);
+        context.in_endpoint.set_average_trb_length(1024);
+        context
+            .in_endpoint
+            .set_tr_dequeue_pointer(in_ring_address, true);
+
+        let out_ring = Ring::new(out_ring_trbs, out_ring_address);
+        let in_ring = Ring::new(in_ring_trbs, in_ring_address);
+
+        let mut erst = EventRingSegmentTable::new(erst_entries);
+        erst.set_segments(&[(
+            event_ring_address,
+            event_ring_trbs.len().try_into().unwrap(),
+        )]);
+
+        debug.dccp.update_volatile(|c| c.set(context_address));
+
+        debug.dcddi1.update_volatile(|d| {
+            d.set_vendor_id(identity.vendor_id);
+            d.set_dbc_protocol(identity.protocol);
+        });
+        debug.dcddi2.update_volatile(|d| {
+            d.set_product_id(identity.product_id);
+            d.set_device_revision(identity.device_revision);
+        });
+
+        debug
+            .dcerstsz
+            .update_volatile(|r| r.set(erst.erstsz()));
+        debug
+            .dcerstba
+            .update_volatile(|r| r.set(erst.erstba(erst_address)));
+        debug
+            .dcerdp
+            .update_volatile(|r| r.set_dequeue_pointer(event_ring_address));
+
+        debug.dcctrl.update_volatile(|c| c.set_debug_capability_enable(true));
+        while !debug.dcctrl.read_volatile().dbc_run() {}
+
+        Self {
+            debug,
+            out_ring,
+            in_ring,
+            event_ring_trbs,
+            event_ring_physical_base: event_ring_address,
+            event_ring_segment_index: 0,
+            event_ring_trb_index: 0,
+            event_ring_cycle_state: true,
+        }
+    }
+
+    /// Enqueues a Normal TRB on the OUT transfer ring (host-to-device) and rings the doorbell.
+    pub fn enqueue_out(&mut self, trb: crate::ring::trb::transfer::Normal) {
+        self.out_ring.enqueue(trb.into());
+        self.debug.dcdb.update_volatile(|d| d.set_doorbell_target(0));
+    }
+
+    /// Enqueues a Normal TRB on the IN transfer ring (device-to-host) and rings the doorbell.
+    pub fn enqueue_in(&mut self, trb: crate::ring::trb::transfer::Normal) {
+        self.in_ring.enqueue(trb.into());
+        self.debug.dcdb.update_volatile(|d| d.set_doorbell_target(1));
+    }
+
+    /// Drains every event the controller has produced so far, passing each to `f`, then writes
+    /// the updated Event Ring Dequeue Pointer back to `dcerdp`.
+    ///
+    /// Writing the dequeue pointer back is what tells the controller the entries were consumed;
+    /// without it, the Event Ring eventually fills, the controller raises Event Ring Full, and
+    /// stops delivering DbC events.
+    pub fn drain_events(&mut self, f: impl FnMut(Trb)) {
+        let segments = [(self.event_ring_physical_base, self.event_ring_trbs)];
+
+        let (dequeue, cycle_state) = advance_dequeue(
+            &segments,
+            self.event_ring_segment_index,
+            self.event_ring_trb_index,
+            self.event_ring_cycle_state,
+            f,
+        );
+
+        self.event_ring_segment_index = usize::from(dequeue.segment_index);
+        self.event_ring_trb_index =
+            ((dequeue.dequeue_pointer - self.event_ring_physical_base) / 16) as usize;
+        self.event_ring_cycle_state = cycle_state;
+
+        self.debug
+            .dcerdp
+            .update_volatile(|r| r.set_dequeue_pointer(dequeue.encode()));
+    }
+}
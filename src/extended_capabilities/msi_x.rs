@@ -0,0 +1,161 @@
+//! MSI-X Table and Pending Bit Array.
+//!
+//! [`super::xhci_extended_message_interrupt::XhciExtendedMessageInterrupt`] only describes where
+//! these two structures live (a BAR index and an offset into it); this module provides the
+//! accessors to actually read and program the MSI-X vectors themselves.
+
+use accessor::Array;
+use accessor::Mapper;
+use bit_field::BitField;
+
+/// The MSI-X Table.
+///
+/// Each entry is 16 bytes: a 32-bit Message Address (low), a 32-bit Message Upper Address, a
+/// 32-bit Message Data, and a 32-bit Vector Control whose only defined bit is the per-vector
+/// Mask bit.
+#[derive(Debug)]
+pub struct MsiXTable<M>(Array<TableEntry, M>)
+where
+    M: Mapper + Clone;
+impl<M> MsiXTable<M>
+where
+    M: Mapper + Clone,
+{
+    /// Creates an accessor to the MSI-X Table located `table_offset` bytes after `bar_base`, the
+    /// start address of the BAR identified by [`super::xhci_extended_message_interrupt::TableOffset::bir`].
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the MSI-X Table is accessed only through the returned
+    /// accessor, and that it actually has `num_vectors` entries (the Table Size field of the
+    /// Message Control register, plus one).
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `bar_base + table_offset` is not 16-byte aligned.
+    pub unsafe fn new(bar_base: usize, table_offset: u32, num_vectors: usize, mapper: M) -> Self {
+        let table_base = bar_base + usize::try_from(table_offset).unwrap();
+        assert!(
+            table_base.trailing_zeros() >= 4,
+            "The MSI-X Table must be 16-byte aligned."
+        );
+
+        Self(Array::new(table_base, num_vectors, mapper))
+    }
+
+    /// Returns the number of entries in the table.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns whether the table has no entries.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.len() == 0
+    }
+
+    /// Returns a copy of the `i`-th entry of the table.
+    #[must_use]
+    pub fn get(&self, i: usize) -> TableEntry {
+        self.0.get(i)
+    }
+
+    /// Updates the `i`-th entry of the table.
+    pub fn update(&mut self, i: usize, f: impl FnOnce(&mut TableEntry)) {
+        self.0.update(i, f);
+    }
+}
+
+/// A single entry of the [`MsiXTable`].
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct TableEntry {
+    message_address_low: u32,
+    message_address_upper: u32,
+    message_data: u32,
+    vector_control: u32,
+}
+impl TableEntry {
+    /// Returns the value of the Message Address field (the lower 32 bits).
+    #[must_use]
+    pub fn message_address_low(self) -> u32 {
+        self.message_address_low
+    }
+
+    /// Sets the value of the Message Address field (the lower 32 bits).
+    pub fn set_message_address_low(&mut self, a: u32) {
+        self.message_address_low = a;
+    }
+
+    /// Returns the value of the Message Upper Address field.
+    #[must_use]
+    pub fn message_address_upper(self) -> u32 {
+        self.message_address_upper
+    }
+
+    /// Sets the value of the Message Upper Address field.
+    pub fn set_message_address_upper(&mut self, a: u32) {
+        self.message_address_upper = a;
+    }
+
+    /// Returns the value of the Message Data field.
+    #[must_use]
+    pub fn message_data(self) -> u32 {
+        self.message_data
+    }
+
+    /// Sets the value of the Message Data field.
+    pub fn set_message_data(&mut self, d: u32) {
+        self.message_data = d;
+    }
+
+    /// Returns the value of the Mask bit.
+    #[must_use]
+    pub fn masked(self) -> bool {
+        self.vector_control.get_bit(0)
+    }
+
+    /// Sets the value of the Mask bit.
+    pub fn set_masked(&mut self, masked: bool) {
+        self.vector_control.set_bit(0, masked);
+    }
+}
+
+/// The Pending Bit Array.
+///
+/// Each 64-bit entry holds the pending bits of 64 vectors; bit `n` of entry `i` is set if vector
+/// `64 * i + n` has a pending, unserviced interrupt.
+#[derive(Debug)]
+pub struct PendingBitArray<M>(Array<u64, M>)
+where
+    M: Mapper + Clone;
+impl<M> PendingBitArray<M>
+where
+    M: Mapper + Clone,
+{
+    /// Creates an accessor to the Pending Bit Array located `pba_offset` bytes after `bar_base`.
+    ///
+    /// `num_vectors` is the total number of MSI-X vectors; the array holds
+    /// `ceil(num_vectors / 64)` qwords.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the Pending Bit Array is accessed only through the returned
+    /// accessor.
+    pub unsafe fn new(bar_base: usize, pba_offset: u32, num_vectors: usize, mapper: M) -> Self {
+        let num_qwords = num_vectors.div_ceil(64);
+
+        Self(Array::new(
+            bar_base + usize::try_from(pba_offset).unwrap(),
+            num_qwords,
+            mapper,
+        ))
+    }
+
+    /// Returns whether `vector` has a pending interrupt.
+    #[must_use]
+    pub fn pending(&self, vector: usize) -> bool {
+        self.0.get(vector / 64).get_bit(vector % 64)
+    }
+}
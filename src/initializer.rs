@@ -0,0 +1,135 @@
+//! The host controller bring-up sequence.
+
+use crate::registers::Registers;
+use crate::ring::Ring;
+use accessor::Mapper;
+
+/// Drives the standard xHCI bring-up sequence: waiting for the controller to become ready,
+/// resetting it, then programming the registers a driver must set up before it can Run.
+///
+/// Each step is its own method rather than one big blocking call, so that a caller running in a
+/// `no_std`/interrupt context can call a step, return, and come back to call the next one (or
+/// re-call a waiting step) without blocking the whole system on hardware latency.
+pub struct HcInitializer<'a, M>
+where
+    M: Mapper + Clone,
+{
+    registers: &'a mut Registers<M>,
+}
+impl<'a, M> HcInitializer<'a, M>
+where
+    M: Mapper + Clone,
+{
+    /// Creates an [`HcInitializer`] that will drive `registers` through its bring-up sequence.
+    #[must_use]
+    pub fn new(registers: &'a mut Registers<M>) -> Self {
+        Self { registers }
+    }
+
+    /// Returns `true` once the Controller Not Ready bit of USBSTS has cleared, meaning the
+    /// controller is ready to accept the Host Controller Reset bit.
+    ///
+    /// The caller should call this repeatedly (e.g. from a poll loop or a timer interrupt) until
+    /// it returns `true` before calling [`HcInitializer::reset`].
+    #[must_use]
+    pub fn controller_is_ready(&self) -> bool {
+        !self.registers.operational.usbsts.read_volatile().controller_not_ready()
+    }
+
+    /// Issues a Host Controller Reset.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if [`HcInitializer::controller_is_ready`] has not yet returned `true`.
+    pub fn reset(&mut self) {
+        assert!(
+            self.controller_is_ready(),
+            "The controller must be ready (CNR cleared) before it can be reset."
+        );
+
+        self.registers
+            .operational
+            .usbcmd
+            .update_volatile(|c| c.set_host_controller_reset(true));
+    }
+
+    /// Returns `true` once the Host Controller Reset bit and Controller Not Ready bit have both
+    /// cleared, meaning the reset has completed and the operational registers may be programmed.
+    ///
+    /// The caller should call this repeatedly until it returns `true` before calling any other
+    /// step of this struct.
+    #[must_use]
+    pub fn reset_completed(&self) -> bool {
+        let usbcmd = self.registers.operational.usbcmd.read_volatile();
+        let usbsts = self.registers.operational.usbsts.read_volatile();
+
+        !usbcmd.host_controller_reset() && !usbsts.controller_not_ready()
+    }
+
+    /// Sets the Max Device Slots Enabled field of the Configure Register (CONFIG).
+    pub fn set_max_device_slots_enabled(&mut self, slots: u8) {
+        self.registers
+            .operational
+            .config
+            .update_volatile(|c| c.set_max_device_slots_enabled(slots));
+    }
+
+    /// Sets the Device Context Base Address Array Pointer Register (DCBAAP).
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `dcbaa_physical_base` is not 64-byte aligned.
+    pub fn set_device_context_base_address_array_pointer(&mut self, dcbaa_physical_base: u64) {
+        self.registers
+            .operational
+            .dcbaap
+            .update_volatile(|d| d.set(dcbaa_physical_base));
+    }
+
+    /// Sets the Command Ring Control Register (CRCR) to point at `ring`, using its physical base
+    /// address and current producer cycle state.
+    pub fn set_command_ring(&mut self, ring: &Ring<'_>) {
+        self.registers.operational.crcr.update_volatile(|c| {
+            c.set_command_ring_pointer(ring.physical_base());
+            c.set_ring_cycle_state(ring.cycle_state());
+        });
+    }
+
+    /// Programs the primary interrupter's Event Ring Segment Table Size, Event Ring Dequeue
+    /// Pointer, and Event Ring Segment Table Base Address registers.
+    ///
+    /// Per the xHCI specification (4.9.4), the Event Ring Dequeue Pointer must be valid before
+    /// the Event Ring Segment Table Base Address is written, since writing ERSTBA is what makes
+    /// the controller start using the table; ERDP is therefore set before ERSTBA.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `erst_physical_base` or `event_ring_dequeue_pointer` is not
+    /// 16-byte aligned.
+    pub fn init_primary_interrupter(
+        &mut self,
+        erst_size: u16,
+        erst_physical_base: u64,
+        event_ring_dequeue_pointer: u64,
+    ) {
+        self.registers
+            .runtime
+            .interrupter_register_set
+            .update_volatile_at(0, |interrupter| {
+                interrupter.erstsz.set(erst_size);
+                interrupter.erdp.set_event_ring_dequeue_pointer(event_ring_dequeue_pointer);
+                interrupter.erstba.set(erst_physical_base);
+            });
+    }
+
+    /// Sets the Run/Stop bit, starting the controller.
+    ///
+    /// The caller is responsible for having completed every previous step first; this method
+    /// does not itself wait for the controller to report that it is running.
+    pub fn run(self) {
+        self.registers
+            .operational
+            .usbcmd
+            .update_volatile(|c| c.set_run_stop(true));
+    }
+}
@@ -3,9 +3,11 @@
 #![no_std]
 
 mod accessor;
+pub mod context;
 pub mod error;
+pub mod initializer;
 pub mod mapper;
 pub mod registers;
+pub mod ring;
 
-/// A struct which initializes the host controller.
-pub struct HcInitializer;
+pub use initializer::HcInitializer;
@@ -0,0 +1,191 @@
+//! The Event Ring Segment Table.
+//!
+//! An Event Ring may be split across multiple physically-discontiguous segments; the Event Ring
+//! Segment Table (ERST) is the array of `(base address, size)` pairs, programmed through
+//! `dcerstba`/`dcerstsz` (Debug Capability) or `erstba`/`erstsz` (the primary interrupter), that
+//! tells the controller where each segment lives.
+
+use super::Trb;
+use bit_field::BitField;
+use core::convert::TryInto;
+
+/// A single entry of the Event Ring Segment Table.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct EventRingSegmentTableEntry {
+    base_address: u64,
+    segment_size: u16,
+    _reserved: [u16; 3],
+}
+impl EventRingSegmentTableEntry {
+    /// Creates an entry describing a segment of `segment_size` [`Trb`]s starting at
+    /// `base_address`.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `base_address` is not 64-byte aligned.
+    #[must_use]
+    pub fn new(base_address: u64, segment_size: u16) -> Self {
+        assert!(
+            base_address.trailing_zeros() >= 6,
+            "The base address of an Event Ring Segment must be 64-byte aligned."
+        );
+
+        Self {
+            base_address,
+            segment_size,
+            _reserved: [0; 3],
+        }
+    }
+
+    /// Returns the value of the Ring Segment Base Address field.
+    #[must_use]
+    pub fn ring_segment_base_address(self) -> u64 {
+        self.base_address
+    }
+
+    /// Returns the value of the Ring Segment Size field.
+    #[must_use]
+    pub fn ring_segment_size(self) -> u16 {
+        self.segment_size
+    }
+}
+
+/// An Event Ring Segment Table builder.
+///
+/// The table itself must live in memory the controller can reach; this type only computes its
+/// contents and the value to program into the ERST Base Address and ERST Size registers.
+#[derive(Debug)]
+pub struct EventRingSegmentTable<'a> {
+    entries: &'a mut [EventRingSegmentTableEntry],
+}
+impl<'a> EventRingSegmentTable<'a> {
+    /// Creates a builder over the (already allocated) backing storage `entries`.
+    #[must_use]
+    pub fn new(entries: &'a mut [EventRingSegmentTableEntry]) -> Self {
+        Self { entries }
+    }
+
+    /// Populates the table with `segments`, where each element is the `(base address, size)` of
+    /// one ring segment.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `segments.len()` does not match the number of entries this table
+    /// was created with.
+    pub fn set_segments(&mut self, segments: &[(u64, u16)]) {
+        assert_eq!(
+            segments.len(),
+            self.entries.len(),
+            "The number of segments must match the size of the Event Ring Segment Table."
+        );
+
+        for (entry, &(base_address, size)) in self.entries.iter_mut().zip(segments) {
+            *entry = EventRingSegmentTableEntry::new(base_address, size);
+        }
+    }
+
+    /// Returns the value to program into `dcerstba`/`erstba`: the physical base address of the
+    /// table itself.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `table_physical_base` is not 16-byte aligned.
+    #[must_use]
+    pub fn erstba(&self, table_physical_base: u64) -> u64 {
+        assert!(
+            table_physical_base.trailing_zeros() >= 4,
+            "The base address of the Event Ring Segment Table must be 16-byte aligned."
+        );
+
+        table_physical_base
+    }
+
+    /// Returns the value to program into `dcerstsz`/`erstsz`: the number of entries in the
+    /// table.
+    #[must_use]
+    pub fn erstsz(&self) -> u16 {
+        self.entries.len().try_into().unwrap()
+    }
+}
+
+/// The current position of an Event Ring consumer within a (possibly multi-segment) Event Ring
+/// Segment Table.
+#[derive(Copy, Clone, Debug)]
+pub struct Dequeue {
+    /// The index, within the Event Ring Segment Table, of the segment the dequeue pointer is
+    /// currently in.
+    pub segment_index: u16,
+    /// The dequeue pointer itself: the address of the next TRB the consumer has not yet read.
+    pub dequeue_pointer: u64,
+    /// The Event Handler Busy bit, which software sets while draining the ring and clears once
+    /// the new dequeue pointer has been written back, per the xHCI specification.
+    pub event_handler_busy: bool,
+}
+impl Dequeue {
+    /// Encodes this position into the value that must be written to
+    /// `dcerdp`/`erdp` (Dequeue ERST Segment Index in bits 0..=2, Event Handler Busy in bit 3,
+    /// and the 16-byte-aligned Dequeue Pointer in the remaining bits).
+    #[must_use]
+    pub fn encode(self) -> u64 {
+        let mut v = self.dequeue_pointer & !0b1111;
+        v.set_bits(0..=2, self.segment_index.into());
+        v.set_bit(3, self.event_handler_busy);
+        v
+    }
+}
+
+/// Walks an Event Ring Segment Table, advancing a [`Dequeue`] position past every TRB whose
+/// Cycle bit matches `consumer_cycle_state`.
+///
+/// `segments` holds, for each segment in table order, the physical base address of its backing
+/// memory paired with the (virtual) slice of [`Trb`]s used to actually read it; the Dequeue
+/// Pointer written back must be a physical address, which is why it is passed in explicitly
+/// rather than derived from the slice (mirrors [`super::Ring::new`] and
+/// [`EventRingSegmentTableEntry::new`]). `handler` is invoked for every such TRB; the walk stops
+/// at the first TRB whose Cycle bit does not match (i.e. the controller has not produced it yet).
+///
+/// Returns the [`Dequeue`] position to write back to the hardware, and the (possibly flipped)
+/// consumer cycle state.
+pub fn advance_dequeue<'a>(
+    segments: &[(u64, &'a [Trb])],
+    mut segment_index: usize,
+    mut trb_index: usize,
+    mut consumer_cycle_state: bool,
+    mut handler: impl FnMut(Trb),
+) -> (Dequeue, bool) {
+    assert!(!segments.is_empty(), "An Event Ring must have at least one segment.");
+
+    loop {
+        let (_, segment) = segments[segment_index];
+        let trb = segment[trb_index];
+
+        if trb.cycle_bit() != consumer_cycle_state {
+            break;
+        }
+
+        handler(trb);
+
+        trb_index += 1;
+        if trb_index == segment.len() {
+            trb_index = 0;
+            segment_index += 1;
+            if segment_index == segments.len() {
+                segment_index = 0;
+                consumer_cycle_state = !consumer_cycle_state;
+            }
+        }
+    }
+
+    let (segment_physical_base, _) = segments[segment_index];
+    let dequeue_pointer = segment_physical_base + (trb_index * 16) as u64;
+
+    (
+        Dequeue {
+            segment_index: segment_index.try_into().unwrap(),
+            dequeue_pointer,
+            event_handler_busy: false,
+        },
+        consumer_cycle_state,
+    )
+}
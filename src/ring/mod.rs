@@ -0,0 +1,98 @@
+//! The Transfer Ring, Command Ring, and Event Ring.
+
+pub mod erst;
+pub mod trb;
+
+pub use trb::Trb;
+
+use trb::Link;
+
+/// A producer ring of [`Trb`]s, used as either a Transfer Ring or a Command Ring.
+///
+/// The last element of the backing storage is reserved for a [`Link`] TRB that points back to
+/// the first element, so that the ring is a cycle. The Link TRB's Toggle Cycle bit is set,
+/// meaning the producer cycle state flips every time [`Ring::enqueue`] wraps around it.
+#[derive(Debug)]
+pub struct Ring<'a> {
+    trbs: &'a mut [Trb],
+    physical_base: u64,
+    enqueue_index: usize,
+    cycle_state: bool,
+}
+impl<'a> Ring<'a> {
+    /// Creates a [`Ring`] backed by `trbs`, initializing the last element to a Link TRB that
+    /// points back to the first element of `trbs`.
+    ///
+    /// `trbs_physical_base` must be the physical address of `trbs[0]`, as the Link TRB must
+    /// contain the physical (not virtual) address of the ring.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `trbs` contains fewer than two elements, or if
+    /// `trbs_physical_base` is not 16-byte aligned.
+    pub fn new(trbs: &'a mut [Trb], trbs_physical_base: u64) -> Self {
+        assert!(
+            trbs.len() >= 2,
+            "A ring must be able to hold at least a Link TRB and one other TRB."
+        );
+
+        let mut link = Link::new(trbs_physical_base);
+        link.set_toggle_cycle(true);
+        *trbs.last_mut().unwrap() = link.into();
+
+        Self {
+            trbs,
+            physical_base: trbs_physical_base,
+            enqueue_index: 0,
+            cycle_state: true,
+        }
+    }
+
+    /// Returns the physical address of the first element of the ring, as given to [`Ring::new`].
+    #[must_use]
+    pub fn physical_base(&self) -> u64 {
+        self.physical_base
+    }
+
+    /// Returns the current producer cycle state: the value [`Trb::cycle_bit`] must have for the
+    /// controller to recognize a TRB as valid.
+    #[must_use]
+    pub fn cycle_state(&self) -> bool {
+        self.cycle_state
+    }
+
+    /// Writes `trb` to the next enqueue slot of the ring with the current producer cycle state,
+    /// then advances the enqueue pointer, wrapping around (and flipping the producer cycle
+    /// state) when the Link TRB is reached.
+    ///
+    /// The Cycle bit is written last, as it is what tells the controller the TRB is ready to be
+    /// consumed.
+    ///
+    /// Returns the index within `trbs` that `trb` was written to.
+    pub fn enqueue(&mut self, mut trb: Trb) -> usize {
+        let index = self.enqueue_index;
+
+        trb.set_cycle_bit(self.cycle_state);
+        let raw = trb.into_raw();
+        let slot: *mut u32 = (&mut self.trbs[index] as *mut Trb).cast();
+        // SAFETY: `Trb` is `#[repr(C)]` over `[u32; 4]`, so `slot` points at four writable dwords.
+        // Volatile writes keep their order, so dword 3 (holding the Cycle bit) is written last.
+        unsafe {
+            for (i, &dword) in raw.iter().enumerate() {
+                slot.add(i).write_volatile(dword);
+            }
+        }
+
+        self.enqueue_index += 1;
+        if self.enqueue_index == self.trbs.len() - 1 {
+            let mut link_trb = self.trbs[self.enqueue_index];
+            link_trb.set_cycle_bit(self.cycle_state);
+            self.trbs[self.enqueue_index] = link_trb;
+
+            self.enqueue_index = 0;
+            self.cycle_state = !self.cycle_state;
+        }
+
+        index
+    }
+}
@@ -0,0 +1,162 @@
+//! TRBs that are placed on the Command Ring.
+
+use super::Dwords;
+use super::Trb;
+use crate::ring::trb::{rw_bit, rw_field};
+use bit_field::BitField;
+
+/// The Enable Slot Command TRB.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct EnableSlot(Trb);
+impl Dwords for EnableSlot {
+    fn dword(&self, i: usize) -> u32 {
+        self.0.dword(i)
+    }
+
+    fn set_dword(&mut self, i: usize, value: u32) {
+        self.0.set_dword(i, value);
+    }
+}
+impl EnableSlot {
+    const TY: u8 = 9;
+
+    /// Creates an Enable Slot Command TRB with every field set to 0 other than the TRB Type.
+    #[must_use]
+    pub fn new() -> Self {
+        let mut t = Self::default();
+        let mut d3 = t.dword(3);
+        d3.set_bits(10..=15, Self::TY.into());
+        t.set_dword(3, d3);
+        t
+    }
+
+    rw_field!(3, 16..=20, slot_type, set_slot_type, "Slot Type", u8);
+}
+impl From<EnableSlot> for Trb {
+    fn from(e: EnableSlot) -> Self {
+        e.0
+    }
+}
+
+/// The Address Device Command TRB.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct AddressDevice(Trb);
+impl Dwords for AddressDevice {
+    fn dword(&self, i: usize) -> u32 {
+        self.0.dword(i)
+    }
+
+    fn set_dword(&mut self, i: usize, value: u32) {
+        self.0.set_dword(i, value);
+    }
+}
+impl AddressDevice {
+    const TY: u8 = 11;
+
+    /// Creates an Address Device Command TRB pointing at `input_context_pointer`.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `input_context_pointer` is not 16-byte aligned.
+    #[must_use]
+    pub fn new(input_context_pointer: u64) -> Self {
+        let mut t = Self::default();
+        let mut d3 = t.dword(3);
+        d3.set_bits(10..=15, Self::TY.into());
+        t.set_dword(3, d3);
+        t.set_input_context_pointer(input_context_pointer);
+        t
+    }
+
+    /// Returns the value of the Input Context Pointer field.
+    #[must_use]
+    pub fn input_context_pointer(self) -> u64 {
+        u64::from(self.dword(0) & !0b1111) | (u64::from(self.dword(1)) << 32)
+    }
+
+    /// Sets the value of the Input Context Pointer field.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `p` is not 16-byte aligned.
+    pub fn set_input_context_pointer(&mut self, p: u64) {
+        assert!(
+            p.trailing_zeros() >= 4,
+            "The Input Context Pointer must be 16-byte aligned."
+        );
+
+        self.set_dword(0, p as u32);
+        self.set_dword(1, (p >> 32) as u32);
+    }
+
+    rw_field!(3, 24..=31, slot_id, set_slot_id, "Slot ID", u8);
+    rw_bit!(3, 9, block_set_address_request, set_block_set_address_request, "Block Set Address Request");
+}
+impl From<AddressDevice> for Trb {
+    fn from(a: AddressDevice) -> Self {
+        a.0
+    }
+}
+
+/// The Configure Endpoint Command TRB.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct ConfigureEndpoint(Trb);
+impl Dwords for ConfigureEndpoint {
+    fn dword(&self, i: usize) -> u32 {
+        self.0.dword(i)
+    }
+
+    fn set_dword(&mut self, i: usize, value: u32) {
+        self.0.set_dword(i, value);
+    }
+}
+impl ConfigureEndpoint {
+    const TY: u8 = 12;
+
+    /// Creates a Configure Endpoint Command TRB pointing at `input_context_pointer`.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `input_context_pointer` is not 16-byte aligned.
+    #[must_use]
+    pub fn new(input_context_pointer: u64) -> Self {
+        let mut t = Self::default();
+        let mut d3 = t.dword(3);
+        d3.set_bits(10..=15, Self::TY.into());
+        t.set_dword(3, d3);
+        t.set_input_context_pointer(input_context_pointer);
+        t
+    }
+
+    /// Returns the value of the Input Context Pointer field.
+    #[must_use]
+    pub fn input_context_pointer(self) -> u64 {
+        u64::from(self.dword(0) & !0b1111) | (u64::from(self.dword(1)) << 32)
+    }
+
+    /// Sets the value of the Input Context Pointer field.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `p` is not 16-byte aligned.
+    pub fn set_input_context_pointer(&mut self, p: u64) {
+        assert!(
+            p.trailing_zeros() >= 4,
+            "The Input Context Pointer must be 16-byte aligned."
+        );
+
+        self.set_dword(0, p as u32);
+        self.set_dword(1, (p >> 32) as u32);
+    }
+
+    rw_field!(3, 24..=31, slot_id, set_slot_id, "Slot ID", u8);
+    rw_bit!(3, 9, deconfigure, set_deconfigure, "Deconfigure");
+}
+impl From<ConfigureEndpoint> for Trb {
+    fn from(c: ConfigureEndpoint) -> Self {
+        c.0
+    }
+}
@@ -0,0 +1,109 @@
+//! TRBs that the controller writes to an Event Ring.
+
+use super::Dwords;
+use super::Trb;
+use crate::ring::trb::ro_field;
+use core::convert::TryFrom;
+
+/// The Transfer Event TRB.
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct TransferEvent(Trb);
+impl Dwords for TransferEvent {
+    fn dword(&self, i: usize) -> u32 {
+        self.0.dword(i)
+    }
+
+    fn set_dword(&mut self, i: usize, value: u32) {
+        self.0.set_dword(i, value);
+    }
+}
+impl TransferEvent {
+    /// Returns the value of the TRB Pointer field.
+    #[must_use]
+    pub fn trb_pointer(self) -> u64 {
+        u64::from(self.dword(0)) | (u64::from(self.dword(1)) << 32)
+    }
+
+    ro_field!(2, 0..=23, trb_transfer_length, "TRB Transfer Length", u32);
+    ro_field!(2, 24..=31, completion_code, "Completion Code", u8);
+    ro_field!(3, 16..=20, endpoint_id, "Endpoint ID", u8);
+    ro_field!(3, 24..=31, slot_id, "Slot ID", u8);
+}
+impl TryFrom<Trb> for TransferEvent {
+    type Error = Trb;
+
+    fn try_from(t: Trb) -> Result<Self, Self::Error> {
+        if t.trb_type() == 32 {
+            Ok(Self(t))
+        } else {
+            Err(t)
+        }
+    }
+}
+
+/// The Command Completion Event TRB.
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct CommandCompletionEvent(Trb);
+impl Dwords for CommandCompletionEvent {
+    fn dword(&self, i: usize) -> u32 {
+        self.0.dword(i)
+    }
+
+    fn set_dword(&mut self, i: usize, value: u32) {
+        self.0.set_dword(i, value);
+    }
+}
+impl CommandCompletionEvent {
+    /// Returns the value of the Command TRB Pointer field.
+    #[must_use]
+    pub fn command_trb_pointer(self) -> u64 {
+        u64::from(self.dword(0) & !0b1111) | (u64::from(self.dword(1)) << 32)
+    }
+
+    ro_field!(2, 0..=23, command_completion_parameter, "Command Completion Parameter", u32);
+    ro_field!(2, 24..=31, completion_code, "Completion Code", u8);
+    ro_field!(3, 16..=23, vf_id, "VF ID", u8);
+    ro_field!(3, 24..=31, slot_id, "Slot ID", u8);
+}
+impl TryFrom<Trb> for CommandCompletionEvent {
+    type Error = Trb;
+
+    fn try_from(t: Trb) -> Result<Self, Self::Error> {
+        if t.trb_type() == 33 {
+            Ok(Self(t))
+        } else {
+            Err(t)
+        }
+    }
+}
+
+/// The Port Status Change Event TRB.
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct PortStatusChangeEvent(Trb);
+impl Dwords for PortStatusChangeEvent {
+    fn dword(&self, i: usize) -> u32 {
+        self.0.dword(i)
+    }
+
+    fn set_dword(&mut self, i: usize, value: u32) {
+        self.0.set_dword(i, value);
+    }
+}
+impl PortStatusChangeEvent {
+    ro_field!(0, 24..=31, port_id, "Port ID", u8);
+    ro_field!(2, 24..=31, completion_code, "Completion Code", u8);
+}
+impl TryFrom<Trb> for PortStatusChangeEvent {
+    type Error = Trb;
+
+    fn try_from(t: Trb) -> Result<Self, Self::Error> {
+        if t.trb_type() == 34 {
+            Ok(Self(t))
+        } else {
+            Err(t)
+        }
+    }
+}
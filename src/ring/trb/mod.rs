@@ -0,0 +1,197 @@
+//! Transfer Request Blocks (TRBs).
+//!
+//! Every TRB is 16 bytes wide and is laid out as four consecutive `u32`s. [`Trb`] is the
+//! untyped, "on the wire" representation that is actually written to and read from a
+//! [`super::Ring`] or an Event Ring (see [`super::erst::advance_dequeue`]); the other types in
+//! this module and its submodules are typed views that can be converted into (or, for events, out
+//! of) a [`Trb`].
+
+pub mod command;
+pub mod event;
+pub mod transfer;
+
+use bit_field::BitField;
+use core::convert::TryInto;
+
+/// A type whose storage is a fixed number of consecutive `u32` dwords, addressable by index.
+///
+/// This lets the `rw_field!`/`ro_field!`/`rw_bit!`/`ro_bit!` macros below target a specific dword
+/// of a multi-dword structure, the same way `extended_capabilities::debug`'s macros of the same
+/// name target the single dword/qword of a register.
+pub(crate) trait Dwords {
+    /// Returns the value of the `i`-th dword.
+    fn dword(&self, i: usize) -> u32;
+    /// Sets the value of the `i`-th dword.
+    fn set_dword(&mut self, i: usize, value: u32);
+}
+
+/// Implements the getter/setter pair for a read-write field inside one dword of a [`Dwords`]
+/// type.
+macro_rules! rw_field {
+    ($dword:expr, $range:expr, $getter:ident, $setter:ident, $name:literal, $ty:ty) => {
+        #[doc = concat!("Returns the value of the ", $name, " field.")]
+        #[must_use]
+        pub fn $getter(self) -> $ty {
+            self.dword($dword).get_bits($range).try_into().unwrap()
+        }
+
+        #[doc = concat!("Sets the value of the ", $name, " field.")]
+        pub fn $setter(&mut self, value: $ty) {
+            let mut d = self.dword($dword);
+            d.set_bits($range, value.into());
+            self.set_dword($dword, d);
+        }
+    };
+}
+pub(crate) use rw_field;
+
+/// Implements the getter for a read-only field inside one dword of a [`Dwords`] type.
+macro_rules! ro_field {
+    ($dword:expr, $range:expr, $getter:ident, $name:literal, $ty:ty) => {
+        #[doc = concat!("Returns the value of the ", $name, " field.")]
+        #[must_use]
+        pub fn $getter(self) -> $ty {
+            self.dword($dword).get_bits($range).try_into().unwrap()
+        }
+    };
+}
+pub(crate) use ro_field;
+
+/// Implements the getter/setter pair for a read-write bit inside one dword of a [`Dwords`] type.
+macro_rules! rw_bit {
+    ($dword:expr, $bit:expr, $getter:ident, $setter:ident, $name:literal) => {
+        #[doc = concat!("Returns the value of the ", $name, " bit.")]
+        #[must_use]
+        pub fn $getter(self) -> bool {
+            self.dword($dword).get_bit($bit)
+        }
+
+        #[doc = concat!("Sets the value of the ", $name, " bit.")]
+        pub fn $setter(&mut self, value: bool) {
+            let mut d = self.dword($dword);
+            d.set_bit($bit, value);
+            self.set_dword($dword, d);
+        }
+    };
+}
+pub(crate) use rw_bit;
+
+/// Implements the getter for a read-only bit inside one dword of a [`Dwords`] type.
+macro_rules! ro_bit {
+    ($dword:expr, $bit:expr, $getter:ident, $name:literal) => {
+        #[doc = concat!("Returns the value of the ", $name, " bit.")]
+        #[must_use]
+        pub fn $getter(self) -> bool {
+            self.dword($dword).get_bit($bit)
+        }
+    };
+}
+pub(crate) use ro_bit;
+
+/// The untyped, 16-byte representation of a TRB.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Trb([u32; 4]);
+impl Dwords for Trb {
+    fn dword(&self, i: usize) -> u32 {
+        self.0[i]
+    }
+
+    fn set_dword(&mut self, i: usize, value: u32) {
+        self.0[i] = value;
+    }
+}
+impl Trb {
+    /// Creates a [`Trb`] whose every field is 0.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the raw four dwords that make up this TRB.
+    #[must_use]
+    pub fn into_raw(self) -> [u32; 4] {
+        self.0
+    }
+
+    /// Creates a [`Trb`] from its raw four dwords.
+    #[must_use]
+    pub fn from_raw(raw: [u32; 4]) -> Self {
+        Self(raw)
+    }
+
+    rw_bit!(3, 0, cycle_bit, set_cycle_bit, "Cycle");
+    ro_field!(3, 10..=15, trb_type, "TRB Type", u8);
+}
+
+/// The Link TRB.
+///
+/// Both Transfer Rings and Command Rings end in a Link TRB that points back to the start of the
+/// ring (or, in a multi-segment ring, to the next segment). Its Cycle bit is the Cycle bit of the
+/// underlying [`Trb`]; convert with `.into()` to read or write it.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Link(Trb);
+impl Dwords for Link {
+    fn dword(&self, i: usize) -> u32 {
+        self.0.dword(i)
+    }
+
+    fn set_dword(&mut self, i: usize, value: u32) {
+        self.0.set_dword(i, value);
+    }
+}
+impl Link {
+    const TY: u8 = 6;
+
+    /// Creates a Link TRB pointing at `ring_segment_pointer`.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `ring_segment_pointer` is not 16-byte aligned.
+    #[must_use]
+    pub fn new(ring_segment_pointer: u64) -> Self {
+        let mut t = Self::default();
+        t.set_ring_segment_pointer(ring_segment_pointer);
+
+        let mut d3 = t.dword(3);
+        d3.set_bits(10..=15, Self::TY.into());
+        t.set_dword(3, d3);
+
+        t
+    }
+
+    /// Returns the value of the Ring Segment Pointer field.
+    #[must_use]
+    pub fn ring_segment_pointer(self) -> u64 {
+        (u64::from(self.dword(1)) << 32) | u64::from(self.dword(0) & !0b1111)
+    }
+
+    /// Sets the value of the Ring Segment Pointer field.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `p` is not 16-byte aligned.
+    pub fn set_ring_segment_pointer(&mut self, p: u64) {
+        assert!(
+            p.trailing_zeros() >= 4,
+            "The Ring Segment Pointer of a Link TRB must be 16-byte aligned."
+        );
+
+        self.set_dword(0, p as u32);
+        self.set_dword(1, (p >> 32) as u32);
+    }
+
+    rw_bit!(
+        3,
+        1,
+        toggle_cycle,
+        set_toggle_cycle,
+        "Toggle Cycle (flip the producer cycle state on wrap)"
+    );
+}
+impl From<Link> for Trb {
+    fn from(l: Link) -> Self {
+        l.0
+    }
+}
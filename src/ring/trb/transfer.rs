@@ -0,0 +1,230 @@
+//! TRBs that are placed on a Transfer Ring.
+
+use super::Dwords;
+use super::Trb;
+use crate::ring::trb::{rw_bit, rw_field};
+use bit_field::BitField;
+
+/// The Normal TRB.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Normal(Trb);
+impl Dwords for Normal {
+    fn dword(&self, i: usize) -> u32 {
+        self.0.dword(i)
+    }
+
+    fn set_dword(&mut self, i: usize, value: u32) {
+        self.0.set_dword(i, value);
+    }
+}
+impl Normal {
+    const TY: u8 = 1;
+
+    /// Creates a Normal TRB with every field set to 0 other than the TRB Type.
+    #[must_use]
+    pub fn new() -> Self {
+        let mut t = Self::default();
+        let mut d3 = t.dword(3);
+        d3.set_bits(10..=15, Self::TY.into());
+        t.set_dword(3, d3);
+        t
+    }
+
+    /// Returns the value of the Data Buffer Pointer field.
+    #[must_use]
+    pub fn data_buffer_pointer(self) -> u64 {
+        u64::from(self.dword(0)) | (u64::from(self.dword(1)) << 32)
+    }
+
+    /// Sets the value of the Data Buffer Pointer field.
+    pub fn set_data_buffer_pointer(&mut self, p: u64) {
+        self.set_dword(0, p as u32);
+        self.set_dword(1, (p >> 32) as u32);
+    }
+
+    rw_field!(2, 0..=16, trb_transfer_length, set_trb_transfer_length, "TRB Transfer Length", u32);
+    rw_field!(2, 22..=31, interrupter_target, set_interrupter_target, "Interrupter Target", u16);
+    rw_bit!(3, 5, interrupt_on_completion, set_interrupt_on_completion, "Interrupt On Completion");
+    rw_bit!(3, 4, chain_bit, set_chain_bit, "Chain");
+}
+impl From<Normal> for Trb {
+    fn from(n: Normal) -> Self {
+        n.0
+    }
+}
+
+/// The Transfer Type field of a Setup Stage TRB.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TransferType {
+    /// No Data Stage.
+    NoDataStage,
+    /// Out Data Stage.
+    Out,
+    /// In Data Stage.
+    In,
+}
+
+/// The Setup Stage TRB.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct SetupStage(Trb);
+impl Dwords for SetupStage {
+    fn dword(&self, i: usize) -> u32 {
+        self.0.dword(i)
+    }
+
+    fn set_dword(&mut self, i: usize, value: u32) {
+        self.0.set_dword(i, value);
+    }
+}
+impl SetupStage {
+    const TY: u8 = 2;
+
+    /// Creates a Setup Stage TRB with the Immediate Data bit set, as required by the xHCI
+    /// specification.
+    #[must_use]
+    pub fn new() -> Self {
+        let mut t = Self::default();
+
+        let mut d3 = t.dword(3);
+        d3.set_bits(10..=15, Self::TY.into());
+        d3.set_bit(6, true);
+        t.set_dword(3, d3);
+
+        let mut d2 = t.dword(2);
+        d2.set_bits(0..=16, 8);
+        t.set_dword(2, d2);
+
+        t
+    }
+
+    /// Sets the value of the bmRequestType field.
+    pub fn set_request_type(&mut self, t: u8) {
+        let mut d0 = self.dword(0);
+        d0.set_bits(0..=7, t.into());
+        self.set_dword(0, d0);
+    }
+
+    /// Sets the value of the bRequest field.
+    pub fn set_request(&mut self, r: u8) {
+        let mut d0 = self.dword(0);
+        d0.set_bits(8..=15, r.into());
+        self.set_dword(0, d0);
+    }
+
+    /// Sets the value of the wValue field.
+    pub fn set_value(&mut self, v: u16) {
+        let mut d0 = self.dword(0);
+        d0.set_bits(16..=31, v.into());
+        self.set_dword(0, d0);
+    }
+
+    /// Sets the value of the wIndex field.
+    pub fn set_index(&mut self, i: u16) {
+        let mut d1 = self.dword(1);
+        d1.set_bits(0..=15, i.into());
+        self.set_dword(1, d1);
+    }
+
+    /// Sets the value of the wLength field.
+    pub fn set_length(&mut self, l: u16) {
+        let mut d1 = self.dword(1);
+        d1.set_bits(16..=31, l.into());
+        self.set_dword(1, d1);
+    }
+
+    /// Sets the value of the Transfer Type field.
+    pub fn set_transfer_type(&mut self, ty: TransferType) {
+        let v: u32 = match ty {
+            TransferType::NoDataStage => 0,
+            TransferType::Out => 2,
+            TransferType::In => 3,
+        };
+
+        let mut d3 = self.dword(3);
+        d3.set_bits(16..=17, v);
+        self.set_dword(3, d3);
+    }
+}
+impl From<SetupStage> for Trb {
+    fn from(s: SetupStage) -> Self {
+        s.0
+    }
+}
+
+/// The Data Stage TRB.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct DataStage(Trb);
+impl Dwords for DataStage {
+    fn dword(&self, i: usize) -> u32 {
+        self.0.dword(i)
+    }
+
+    fn set_dword(&mut self, i: usize, value: u32) {
+        self.0.set_dword(i, value);
+    }
+}
+impl DataStage {
+    const TY: u8 = 3;
+
+    /// Creates a Data Stage TRB with every field set to 0 other than the TRB Type.
+    #[must_use]
+    pub fn new() -> Self {
+        let mut t = Self::default();
+        let mut d3 = t.dword(3);
+        d3.set_bits(10..=15, Self::TY.into());
+        t.set_dword(3, d3);
+        t
+    }
+
+    /// Sets the value of the Data Buffer Pointer field.
+    pub fn set_data_buffer_pointer(&mut self, p: u64) {
+        self.set_dword(0, p as u32);
+        self.set_dword(1, (p >> 32) as u32);
+    }
+
+    rw_field!(2, 0..=16, trb_transfer_length, set_trb_transfer_length, "TRB Transfer Length", u32);
+    rw_bit!(3, 16, direction, set_direction, "Direction (set for an IN transfer)");
+}
+impl From<DataStage> for Trb {
+    fn from(d: DataStage) -> Self {
+        d.0
+    }
+}
+
+/// The Status Stage TRB.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct StatusStage(Trb);
+impl Dwords for StatusStage {
+    fn dword(&self, i: usize) -> u32 {
+        self.0.dword(i)
+    }
+
+    fn set_dword(&mut self, i: usize, value: u32) {
+        self.0.set_dword(i, value);
+    }
+}
+impl StatusStage {
+    const TY: u8 = 4;
+
+    /// Creates a Status Stage TRB with every field set to 0 other than the TRB Type.
+    #[must_use]
+    pub fn new() -> Self {
+        let mut t = Self::default();
+        let mut d3 = t.dword(3);
+        d3.set_bits(10..=15, Self::TY.into());
+        t.set_dword(3, d3);
+        t
+    }
+
+    rw_bit!(3, 16, direction, set_direction, "Direction (set for an IN transfer)");
+    rw_bit!(3, 5, interrupt_on_completion, set_interrupt_on_completion, "Interrupt On Completion");
+}
+impl From<StatusStage> for Trb {
+    fn from(s: StatusStage) -> Self {
+        s.0
+    }
+}